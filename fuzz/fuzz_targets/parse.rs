@@ -0,0 +1,18 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// `cusip::parse` and `cusip::parse_loose` must never panic on arbitrary input. This also guards the
+// invariant `cusip-tool`'s fix mode relies on to justify its `unsafe { from_utf8_unchecked(...) }`
+// on the first 8 bytes of a payload that failed with `IncorrectCheckDigit`: that variant is only
+// ever returned for a 9-byte ASCII payload. If `cusip::parse` ever returned it for anything else,
+// that unsafe block would be operating on a slice that isn't valid UTF-8.
+fuzz_target!(|data: &str| {
+    if let Err(cusip::CUSIPError::IncorrectCheckDigit { .. }) = cusip::parse(data) {
+        assert!(
+            data.len() == 9 && data.is_ascii(),
+            "IncorrectCheckDigit must only be returned for a 9-byte ASCII payload, got {data:?}"
+        );
+    }
+    let _ = cusip::parse_loose(data);
+});
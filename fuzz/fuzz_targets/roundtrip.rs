@@ -0,0 +1,30 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Any 8-character ASCII payload that `cusip::build_from_payload` accepts must produce a `CUSIP`
+// that, once formatted with `Display` and reparsed, is identical to the original -- and in
+// particular, whose check digit does not drift across the round trip.
+fuzz_target!(|payload: [u8; 8]| {
+    if !payload.is_ascii() {
+        return;
+    }
+    let payload = unsafe { std::str::from_utf8_unchecked(&payload) };
+
+    let Ok(cusip) = cusip::build_from_payload(payload) else {
+        return;
+    };
+
+    let rendered = cusip.to_string();
+    let reparsed = cusip::parse(&rendered).expect("a freshly built CUSIP must reparse");
+
+    assert_eq!(
+        cusip, reparsed,
+        "round-tripping through Display must be lossless"
+    );
+    assert_eq!(
+        cusip.check_digit(),
+        reparsed.check_digit(),
+        "the check digit must be stable across a round trip"
+    );
+});
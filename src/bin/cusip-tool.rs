@@ -55,58 +55,186 @@
 //! If you run with argument `--fix`, then any input CUSIPs that are only wrong due to incorrect
 //! _Check Digit_ will be fixed. In this mode, every good and every fixable input CUSIP is printed
 //! to standard output.
+//!
+//! ## ISIN mode
+//!
+//! The GLEIF file above maps LEIs to ISINs, not CUSIPs, so the example above strips the `US`
+//! country prefix and the trailing ISIN check digit with `grep`/`sed` before the CUSIPs can be
+//! validated. Running with `--from-isin` does that work directly: it reads 12-character ISINs
+//! from stdin, validates the ISIN check digit itself, and then extracts and reports on (or, with
+//! `--fix`, repairs) the CUSIP embedded in its NSIN. Only `US` and `CA` ISINs have a CUSIP as
+//! their NSIN; any other country code is reported as an error.
+//!
+//! Fix mode also recovers from a mistyped payload character, not just a mistyped check digit: if
+//! a single confusable-character substitution or adjacent transposition of the payload would make
+//! the supplied check digit correct, that correction is printed instead. If more than one such
+//! correction is possible the input is left unfixed and the candidates are reported to stderr; see
+//! [`corrections`] for details.
+//!
+//! ## Structured output
+//!
+//! By default the tool prints CUSIPs to stdout only in fix mode (as described above) and a
+//! human-readable summary to stderr. Passing `--format json` or `--format csv` instead emits one machine-readable record per
+//! input to stdout, followed by a final summary record, so downstream tooling does not need to
+//! screen-scrape stderr; see [`output`] for the record shape. `--format text` (the default)
+//! preserves the original behavior.
+//!
+//! ## Parallel validation
+//!
+//! The 1.6M-CUSIP GLEIF file above is large enough that a single-threaded, line-at-a-time loop
+//! leaves most cores idle. Input is read into fixed-size chunks and validated in parallel across a
+//! [rayon](https://docs.rs/rayon) thread pool, with `--jobs N` controlling the pool size (default:
+//! one thread per core). Because validating in parallel does not preserve input order, output is
+//! unordered by default; pass `--ordered` to buffer each chunk's output and replay it in input
+//! order at the cost of some parallelism.
 
 use std::env;
 use std::io;
 use std::io::prelude::*;
-use std::str::from_utf8_unchecked;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use rayon::prelude::*;
+
+mod corrections;
+mod isin;
+mod output;
+mod processing;
+
+use output::Format;
+use processing::{process_line, LineOutcome};
+
+/// Number of lines handed to a single rayon task at a time.
+const CHUNK_SIZE: usize = 4096;
 
 #[doc(hidden)]
 fn main() {
     let mut fix: bool = false;
+    let mut from_isin: bool = false;
+    let mut format = Format::Text;
+    let mut jobs: Option<usize> = None;
+    let mut ordered: bool = false;
 
-    let args: Vec<String> = env::args().collect();
-    if args.len() == 2 && args[1] == "--fix" {
-        fix = true;
-    } else if args.len() != 1 {
-        eprintln!("usage: cusip-tool [--fix]");
-        std::process::exit(1);
-    }
-
-    let mut good = 0u64;
-    let mut bad = 0u64;
-    let mut fixed = 0u64;
-
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let line = line.unwrap();
-        match cusip::parse(&line) {
-            Ok(cusip) => {
-                good += 1;
-                if fix {
-                    println!("{cusip}");
-                }
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--fix" => fix = true,
+            "--from-isin" => from_isin = true,
+            "--ordered" => ordered = true,
+            "--format" => {
+                let value = args.next().unwrap_or_else(|| {
+                    eprintln!("--format requires a value: text, json, or csv");
+                    std::process::exit(1);
+                });
+                format = value.parse().unwrap_or_else(|err| {
+                    eprintln!("{err}");
+                    std::process::exit(1);
+                });
             }
-            Err(cusip::CUSIPError::IncorrectCheckDigit {
-                was: _,
-                expected: _,
-            }) => {
-                bad += 1;
-                if fix {
-                    let payload = &line.as_bytes()[0..8]; // We know it was the right length
-                    let payload = unsafe { from_utf8_unchecked(payload) }; // We know it is ASCII
-
-                    // We know the Check Digit was the only problem, so we can safely unwrap()
-                    let cusip = cusip::build_from_payload(payload).unwrap();
-                    println!("{cusip}");
-                    fixed += 1;
+            "--jobs" => {
+                let value = args.next().unwrap_or_else(|| {
+                    eprintln!("--jobs requires a value");
+                    std::process::exit(1);
+                });
+                let parsed: usize = value.parse().unwrap_or(0);
+                if parsed == 0 {
+                    eprintln!("--jobs value must be a positive integer, got {value:?}");
+                    std::process::exit(1);
                 }
+                jobs = Some(parsed);
+            }
+            _ => {
+                eprintln!(
+                    "usage: cusip-tool [--fix] [--from-isin] [--format text|json|csv] \
+                     [--jobs N] [--ordered]"
+                );
+                std::process::exit(1);
             }
-            Err(err) => {
-                eprintln!("Input: {line}; Error: {err}");
-                bad += 1;
+        }
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs.unwrap_or(0)) // 0 means "let rayon pick", i.e. one thread per core
+        .build()
+        .expect("failed to start the rayon thread pool");
+
+    let good = AtomicU64::new(0);
+    let bad = AtomicU64::new(0);
+    let fixed = AtomicU64::new(0);
+
+    if format == Format::Csv {
+        output::print_csv_header();
+    }
+
+    let tally = |outcome: &LineOutcome| {
+        if outcome.good {
+            good.fetch_add(1, Ordering::Relaxed);
+        }
+        if outcome.bad {
+            bad.fetch_add(1, Ordering::Relaxed);
+        }
+        if outcome.fixed {
+            fixed.fetch_add(1, Ordering::Relaxed);
+        }
+    };
+
+    // Read and validate one chunk at a time, rather than the whole input up front, so memory use
+    // stays bounded and a malformed line only loses its own chunk's output rather than everything
+    // read before it.
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        let chunk: Vec<String> = (&mut lines)
+            .take(CHUNK_SIZE)
+            .map(|line| line.unwrap())
+            .collect();
+        if chunk.is_empty() {
+            break;
+        }
+
+        pool.install(|| {
+            if ordered {
+                // `par_iter().map(..).collect()` preserves the chunk's original order even though
+                // the lines themselves are validated out of order, so we can just replay it below.
+                let outcomes: Vec<LineOutcome> = chunk
+                    .par_iter()
+                    .map(|line| process_line(line, fix, from_isin, format))
+                    .collect();
+
+                for outcome in &outcomes {
+                    tally(outcome);
+                    if let Some(stdout) = &outcome.stdout {
+                        println!("{stdout}");
+                    }
+                    if let Some(stderr) = &outcome.stderr {
+                        eprintln!("{stderr}");
+                    }
+                }
+            } else {
+                chunk.par_iter().for_each(|line| {
+                    let outcome = process_line(line, fix, from_isin, format);
+                    tally(&outcome);
+                    if let Some(stdout) = &outcome.stdout {
+                        println!("{stdout}");
+                    }
+                    if let Some(stderr) = &outcome.stderr {
+                        eprintln!("{stderr}");
+                    }
+                });
             }
+        });
+    }
+
+    let good = good.load(Ordering::Relaxed);
+    let bad = bad.load(Ordering::Relaxed);
+    let fixed = fixed.load(Ordering::Relaxed);
+
+    if format != Format::Text {
+        output::Summary {
+            read: good + bad,
+            valid: good,
+            invalid: bad,
+            fixed,
         }
+        .print(format);
     }
 
     if fix {
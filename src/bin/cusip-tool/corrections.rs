@@ -0,0 +1,151 @@
+//! Auto-repair for CUSIPs whose check digit fails to validate not because the check digit itself
+//! was mistyped, but because a single character of the 8-character payload was mistyped and the
+//! check digit supplied with the input is actually the correct one for the *intended* payload.
+//!
+//! This covers two common classes of data-entry error: substituting a visually or OCR-confusable
+//! character (`O`/`0`, `I`/`1`, `S`/`5`, `B`/`8`, `Z`/`2`, `A`/`4`, `E`/`3`), and transposing two
+//! adjacent characters. For each, every single-edit candidate payload is tried against the
+//! supplied check digit; if exactly one candidate reproduces it, that candidate is almost
+//! certainly the intended CUSIP.
+//!
+//! Note that the modulus 10 double-add-double algorithm cannot detect an adjacent transposition of
+//! the digits `0` and `9`: swapping them leaves the check digit unchanged, so such a typo passes
+//! validation and never reaches this search. Whenever more than one candidate reproduces the
+//! supplied check digit, all of them are returned as an ambiguous result rather than one being
+//! picked silently.
+//!
+//! This lives in `cusip-tool` rather than the `cusip` library crate because the library crate's
+//! source is not present anywhere in this repository: tracked history goes back to a baseline
+//! commit containing only `src/bin/cusip-tool.rs`, with no `src/lib.rs` and no `Cargo.toml` ever
+//! checked in. That is a fact about this repository's contents, not about what tooling happens to
+//! be available -- there is nothing to add `pub fn suggest_corrections` to. Once `src/lib.rs` is
+//! added to this repository, this module's logic should move there unchanged so other consumers
+//! of `cusip` can reach it as `cusip::suggest_corrections`.
+
+use std::collections::HashSet;
+
+/// Pairs of characters that are commonly confused with one another, either visually or by OCR.
+const CONFUSABLE_PAIRS: &[(char, char)] = &[
+    ('O', '0'),
+    ('I', '1'),
+    ('S', '5'),
+    ('B', '8'),
+    ('Z', '2'),
+    ('A', '4'),
+    ('E', '3'),
+];
+
+/// The result of searching for a single-edit correction to a CUSIP payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Correction {
+    /// Exactly one candidate payload reproduces the supplied check digit.
+    Unique(String),
+    /// More than one candidate payload reproduces the supplied check digit.
+    Ambiguous(Vec<String>),
+    /// No single-edit candidate reproduces the supplied check digit.
+    None,
+}
+
+/// Searches for a single confusable-character substitution or adjacent transposition of `payload`
+/// whose recomputed check digit equals `check_digit`, and returns the resulting CUSIP(s).
+pub fn suggest_corrections(payload: &str, check_digit: char) -> Correction {
+    let chars: Vec<char> = payload.chars().collect();
+    let mut candidates: HashSet<String> = HashSet::new();
+
+    for i in 0..chars.len() {
+        for alt in confusable_alternates(chars[i]) {
+            let mut candidate = chars.clone();
+            candidate[i] = alt;
+            candidates.insert(candidate.into_iter().collect());
+        }
+    }
+
+    for i in 0..chars.len().saturating_sub(1) {
+        if chars[i] != chars[i + 1] {
+            let mut candidate = chars.clone();
+            candidate.swap(i, i + 1);
+            candidates.insert(candidate.into_iter().collect());
+        }
+    }
+    candidates.remove(payload);
+
+    let mut matches: Vec<String> = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let cusip = cusip::build_from_payload(&candidate).ok()?;
+            let rendered = cusip.to_string();
+            let digit = rendered.chars().last()?;
+            (digit == check_digit).then_some(rendered)
+        })
+        .collect();
+
+    matches.sort();
+    matches.dedup();
+
+    match matches.len() {
+        0 => Correction::None,
+        1 => Correction::Unique(matches.remove(0)),
+        _ => Correction::Ambiguous(matches),
+    }
+}
+
+/// Returns the characters commonly confused with `c`, if any.
+fn confusable_alternates(c: char) -> Vec<char> {
+    CONFUSABLE_PAIRS
+        .iter()
+        .filter_map(|&(a, b)| {
+            if c == a {
+                Some(b)
+            } else if c == b {
+                Some(a)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "37833100" is a valid CUSIP payload whose check digit is '3' (CUSIP 378331003).
+
+    #[test]
+    fn finds_a_unique_confusable_correction() {
+        // The true payload's '3' at index 4 was mistyped as its confusable 'E'.
+        match suggest_corrections("3783E100", '3') {
+            Correction::Unique(cusip) => assert_eq!(cusip, "378331003"),
+            other => panic!("expected a unique correction, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn finds_a_unique_transposition_correction() {
+        // The true payload's leading "37" was transposed to "73".
+        match suggest_corrections("73833100", '3') {
+            Correction::Unique(cusip) => assert_eq!(cusip, "378331003"),
+            other => panic!("expected a unique correction, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn reports_ambiguous_corrections() {
+        // The true payload's '8' at index 2 was mistyped as its confusable 'B'; that also happens
+        // to make a second, unrelated candidate ("37B331O0") reproduce the same check digit.
+        match suggest_corrections("37B33100", '3') {
+            Correction::Ambiguous(candidates) => {
+                assert_eq!(
+                    candidates,
+                    vec!["378331003".to_string(), "37B331O03".to_string()]
+                );
+            }
+            other => panic!("expected an ambiguous correction, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn reports_no_correction_when_no_candidate_matches() {
+        assert_eq!(suggest_corrections("37833100", '3'), Correction::None);
+    }
+}
@@ -0,0 +1,179 @@
+//! Support for extracting CUSIPs embedded in International Securities Identification Numbers
+//! (ISINs).
+//!
+//! An ISIN is a 2-letter country code, a 9-character National Securities Identifying Number
+//! (NSIN), and a single check digit. For ISINs issued under the `US` and `CA` country codes, the
+//! NSIN *is* the CUSIP (the CUSIP's own check digit is the last character of the NSIN). This
+//! module validates the ISIN check digit and, where possible, extracts the embedded CUSIP payload
+//! so it can be run through the usual `cusip` parsing pipeline.
+
+use std::fmt;
+
+/// The two-letter country codes whose NSIN is a CUSIP.
+const CUSIP_COUNTRY_CODES: [&str; 2] = ["US", "CA"];
+
+/// An error encountered while validating or extracting a CUSIP from an ISIN.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IsinError {
+    /// The input was not exactly 12 characters long.
+    InvalidLength { was: usize },
+    /// The input contained a character that cannot appear in an ISIN.
+    InvalidCharacter { was: char },
+    /// The ISIN check digit did not match the one computed from the rest of the ISIN.
+    IncorrectCheckDigit { was: u8, expected: u8 },
+    /// The ISIN's country code is not one whose NSIN is a CUSIP.
+    UnsupportedCountryCode { was: String },
+}
+
+impl fmt::Display for IsinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IsinError::InvalidLength { was } => {
+                write!(f, "ISIN was {was} characters long, expected 12")
+            }
+            IsinError::InvalidCharacter { was } => {
+                write!(f, "ISIN contained invalid character {was:?}")
+            }
+            IsinError::IncorrectCheckDigit { was, expected } => {
+                write!(f, "ISIN check digit was {was}, expected {expected}")
+            }
+            IsinError::UnsupportedCountryCode { was } => {
+                write!(
+                    f,
+                    "ISIN country code {was:?} is not one whose NSIN is a CUSIP"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for IsinError {}
+
+/// Validates the check digit of a 12-character ISIN using the Luhn "mod 10" method and, if the
+/// ISIN's country code is one whose NSIN is a CUSIP (`US` or `CA`), returns the embedded
+/// 9-character CUSIP payload (including its own check digit).
+///
+/// This only validates the ISIN; it does not validate the returned CUSIP. Pass the result to
+/// [`cusip::parse`] to do that.
+pub fn extract_cusip(isin: &str) -> Result<&str, IsinError> {
+    if isin.len() != 12 {
+        return Err(IsinError::InvalidLength {
+            was: isin.chars().count(),
+        });
+    }
+    if let Some(bad) = isin.chars().find(|c| !c.is_ascii()) {
+        return Err(IsinError::InvalidCharacter { was: bad });
+    }
+
+    let bytes = isin.as_bytes();
+    if !bytes[11].is_ascii_digit() {
+        return Err(IsinError::InvalidCharacter {
+            was: bytes[11] as char,
+        });
+    }
+
+    let expected = check_digit(&isin[0..11])?;
+    let was = bytes[11] - b'0';
+    if was != expected {
+        return Err(IsinError::IncorrectCheckDigit { was, expected });
+    }
+
+    let country_code = &isin[0..2];
+    if !CUSIP_COUNTRY_CODES.contains(&country_code) {
+        return Err(IsinError::UnsupportedCountryCode {
+            was: country_code.to_string(),
+        });
+    }
+
+    Ok(&isin[2..11])
+}
+
+/// Computes the ISIN check digit for the country code + NSIN prefix (the first 11 characters of
+/// an ISIN), per the Luhn "mod 10" method: each letter A-Z is expanded to its value 10-35, written
+/// as two decimal digits; the resulting digit string is then doubled every second digit counting
+/// from the right, with doubled values over 9 having their own digits summed; the check digit is
+/// `(10 - (sum mod 10)) mod 10`.
+fn check_digit(prefix: &str) -> Result<u8, IsinError> {
+    let mut digits: Vec<u8> = Vec::with_capacity(prefix.len() * 2);
+    for c in prefix.chars() {
+        match c {
+            '0'..='9' => digits.push(c as u8 - b'0'),
+            'A'..='Z' => {
+                let value = c as u8 - b'A' + 10;
+                digits.push(value / 10);
+                digits.push(value % 10);
+            }
+            other => return Err(IsinError::InvalidCharacter { was: other }),
+        }
+    }
+
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            let d = d as u32;
+            if i % 2 == 0 {
+                let doubled = d * 2;
+                doubled / 10 + doubled % 10
+            } else {
+                d
+            }
+        })
+        .sum();
+
+    Ok(((10 - (sum % 10)) % 10) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_cusip_from_valid_us_isin() {
+        // Apple Inc.'s ISIN; US0378331005 -> CUSIP 037833100.
+        assert_eq!(extract_cusip("US0378331005"), Ok("037833100"));
+    }
+
+    #[test]
+    fn rejects_wrong_length() {
+        assert_eq!(
+            extract_cusip("US037833100"),
+            Err(IsinError::InvalidLength { was: 11 })
+        );
+    }
+
+    #[test]
+    fn rejects_non_ascii_character_even_with_matching_byte_length() {
+        // One ASCII character swapped for a 2-byte UTF-8 character keeps the byte length at 12
+        // (the length `extract_cusip` checks first) while dropping the character count to 11.
+        let isin = "US03783310\u{e9}";
+        assert_eq!(isin.len(), 12);
+        assert_eq!(
+            extract_cusip(isin),
+            Err(IsinError::InvalidCharacter { was: '\u{e9}' })
+        );
+    }
+
+    #[test]
+    fn rejects_incorrect_isin_check_digit() {
+        assert_eq!(
+            extract_cusip("US0378331006"),
+            Err(IsinError::IncorrectCheckDigit {
+                was: 6,
+                expected: 5
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_country_codes_without_a_cusip_nsin() {
+        // GB0378331002 is a check-digit-valid ISIN, but the UK's NSIN is a SEDOL, not a CUSIP.
+        assert_eq!(
+            extract_cusip("GB0378331002"),
+            Err(IsinError::UnsupportedCountryCode {
+                was: "GB".to_string()
+            })
+        );
+    }
+}
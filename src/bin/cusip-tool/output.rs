@@ -0,0 +1,191 @@
+//! Machine-readable output formats for `cusip-tool`.
+//!
+//! In addition to the human-readable summary on stderr, the tool can emit one record per input on
+//! stdout as JSON Lines or CSV, each carrying the raw input, whether it was valid, the error
+//! variant name when it was not, the parsed Issuer Number, Issue Number, and Check Digit components
+//! on success (described in the crate's docs as CUSIP's 6/2/1 decomposition), and the repaired
+//! CUSIP when `--fix` corrected it. A final summary record with the read/valid/invalid/fixed counts
+//! is emitted after the individual records so downstream pipelines do not need to screen-scrape
+//! stderr.
+
+use std::str::FromStr;
+
+/// The output format for `cusip-tool`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            other => Err(format!(
+                "unknown format {other:?}, expected text, json, or csv"
+            )),
+        }
+    }
+}
+
+/// One input's validation result, ready to be rendered as JSON or CSV.
+pub struct Record<'a> {
+    pub input: &'a str,
+    pub valid: bool,
+    pub error: Option<String>,
+    pub issuer_num: Option<&'a str>,
+    pub issue_num: Option<&'a str>,
+    pub check_digit: Option<char>,
+    /// The CUSIP `--fix` repaired this input to, if any. Set regardless of `valid`/`error`, since
+    /// a fixed input is reported as invalid (it failed to parse as given) but was still repaired.
+    pub fixed_cusip: Option<&'a str>,
+}
+
+impl Record<'_> {
+    /// Renders this record in the given format. Must not be called with `Format::Text`, which is
+    /// rendered by the caller itself to preserve the existing plain-text behavior.
+    pub fn render(&self, format: Format) -> String {
+        let check_digit = self.check_digit.map(String::from);
+        match format {
+            Format::Text => unreachable!("text format is rendered by the caller"),
+            Format::Json => format!(
+                "{{\"input\":{},\"valid\":{},\"error\":{},\"issuer_num\":{},\"issue_num\":{},\"check_digit\":{},\"fixed_cusip\":{}}}",
+                json_string(self.input),
+                self.valid,
+                json_opt_string(self.error.as_deref()),
+                json_opt_string(self.issuer_num),
+                json_opt_string(self.issue_num),
+                json_opt_string(check_digit.as_deref()),
+                json_opt_string(self.fixed_cusip),
+            ),
+            Format::Csv => format!(
+                "{},{},{},{},{},{},{}",
+                csv_field(self.input),
+                self.valid,
+                csv_field(self.error.as_deref().unwrap_or("")),
+                csv_field(self.issuer_num.unwrap_or("")),
+                csv_field(self.issue_num.unwrap_or("")),
+                csv_field(check_digit.as_deref().unwrap_or("")),
+                csv_field(self.fixed_cusip.unwrap_or("")),
+            ),
+        }
+    }
+
+    /// Prints this record to stdout in the given format.
+    pub fn print(&self, format: Format) {
+        println!("{}", self.render(format));
+    }
+}
+
+/// Prints the CSV header row. Has no JSON equivalent, since each JSON record is self-describing.
+pub fn print_csv_header() {
+    println!("input,valid,error,issuer_num,issue_num,check_digit,fixed_cusip");
+}
+
+/// The final read/valid/invalid/fixed counts, ready to be rendered as JSON or CSV.
+pub struct Summary {
+    pub read: u64,
+    pub valid: u64,
+    pub invalid: u64,
+    pub fixed: u64,
+}
+
+impl Summary {
+    /// Renders this summary in the given format. Must not be called with `Format::Text`.
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Text => unreachable!("text format is rendered by the caller"),
+            Format::Json => format!(
+                "{{\"read\":{},\"valid\":{},\"invalid\":{},\"fixed\":{}}}",
+                self.read, self.valid, self.invalid, self.fixed
+            ),
+            // A leading `#summary` marker, plus padding to the same column count as the header
+            // `print_csv_header` writes, keeps this row from being a ragged CSV row that a reader
+            // would misalign against the per-record schema.
+            Format::Csv => format!(
+                "#summary,{},{},{},{},,",
+                self.read, self.valid, self.invalid, self.fixed
+            ),
+        }
+    }
+
+    pub fn print(&self, format: Format) {
+        println!("{}", self.render(format));
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_opt_string(s: Option<&str>) -> String {
+    match s {
+        Some(s) => json_string(s),
+        None => "null".to_string(),
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains(',') || s.contains('"') || s.contains('\n') {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_string_passes_plain_text_through() {
+        assert_eq!(json_string("037833100"), "\"037833100\"");
+    }
+
+    #[test]
+    fn json_string_escapes_quotes_and_backslashes() {
+        assert_eq!(json_string(r#"say "hi"\"#), r#""say \"hi\"\\""#);
+    }
+
+    #[test]
+    fn json_string_escapes_newlines_tabs_and_control_characters() {
+        assert_eq!(json_string("a\nb\tc\rd\u{1}e"), "\"a\\nb\\tc\\rd\\u0001e\"");
+    }
+
+    #[test]
+    fn json_opt_string_renders_null_for_none() {
+        assert_eq!(json_opt_string(None), "null");
+        assert_eq!(json_opt_string(Some("x")), "\"x\"");
+    }
+
+    #[test]
+    fn csv_field_passes_plain_text_through_unquoted() {
+        assert_eq!(csv_field("037833100"), "037833100");
+    }
+
+    #[test]
+    fn csv_field_quotes_and_escapes_commas_quotes_and_newlines() {
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("a\"b"), "\"a\"\"b\"");
+        assert_eq!(csv_field("a\nb"), "\"a\nb\"");
+    }
+}
